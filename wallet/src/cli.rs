@@ -8,7 +8,10 @@ use clap::{ArgAction::Append, Args, Parser, Subcommand};
 use sp_core::H256;
 use tuxedo_core::types::OutputRef;
 
-use crate::{h256_from_string, keystore::SHAWN_PUB_KEY, output_ref_from_string, DEFAULT_ENDPOINT};
+use crate::{
+    coin_select::SelectionStrategy, h256_from_string, keystore::SHAWN_PUB_KEY,
+    output_ref_from_string, subscribe::Topic, DEFAULT_ENDPOINT,
+};
 
 /// The default number of coins to be minted.
 pub const DEFAULT_MINT_VALUE: &str = "100";
@@ -98,8 +101,7 @@ pub enum Command {
 
     //Some(Command::MintCoins { amount }) => money::mint_coins(&db, &client, &keystore,amount).await,
     /// Spend some coins.
-    /// For now, all outputs in a single transaction go to the same recipient.
-    // FixMe: #62
+    /// Outputs may go to multiple recipients; see `--to`.
     #[command(verbatim_doc_comment)]
     SpendCoins(SpendArgs),
 
@@ -174,6 +176,58 @@ pub enum Command {
     /// Buy Kitty.
     #[command(verbatim_doc_comment)]
     BuyKitty(BuyKittyArgs),
+
+    /// Build an unsigned transaction from the given inputs and outputs and write it to a file,
+    /// without signing or submitting it. Use `SignTransaction` and `BroadcastTransaction` to
+    /// complete the offline workflow.
+    #[command(verbatim_doc_comment)]
+    BuildTransaction(BuildTransactionArgs),
+
+    /// Load an unsigned (or partially-signed) transaction from a file, sign it with keys from
+    /// the local keystore, and write the result back out. Does not require a node connection.
+    #[command(verbatim_doc_comment)]
+    SignTransaction(SignTransactionArgs),
+
+    /// Submit a previously signed transaction file to the node.
+    #[command(verbatim_doc_comment)]
+    BroadcastTransaction(BroadcastTransactionArgs),
+
+    /// Report the projected fee for a given set of inputs and outputs, without building or
+    /// sending anything.
+    #[command(verbatim_doc_comment)]
+    EstimateFee(EstimateFeeArgs),
+
+    /// Open a long-running stream of block and UTXO events as the wallet syncs, instead of
+    /// polling `ShowBalance`/`ShowAllKitties` in a loop.
+    #[command(verbatim_doc_comment)]
+    Subscribe(SubscribeArgs),
+
+    /// Track a public key for indexing without inserting any secret. Watch-only keys are
+    /// included in `ShowBalance`/`ShowOwnedKitties`/`ShowAllOutputs` and are flagged as
+    /// unspendable in `ShowKeys`.
+    #[command(verbatim_doc_comment)]
+    WatchAddress {
+        /// The public key to start tracking.
+        #[arg(value_parser = h256_from_string)]
+        pub_key: H256,
+    },
+
+    /// Stop tracking a watch-only public key previously added with `WatchAddress`.
+    /// Keys with private material in the keystore cannot be unwatched this way; use `RemoveKey`.
+    #[command(verbatim_doc_comment)]
+    UnwatchAddress {
+        /// The public key to stop tracking.
+        #[arg(value_parser = h256_from_string)]
+        pub_key: H256,
+    },
+
+    /// Expand a compact descriptor string into a set of sr25519 public keys and track all of
+    /// them as watch-only, the same as repeated calls to `WatchAddress`.
+    #[command(verbatim_doc_comment)]
+    ImportDescriptor {
+        /// The descriptor string to expand.
+        descriptor: String,
+    },
 }
 
 #[derive(Debug, Args)]
@@ -192,7 +246,8 @@ pub struct MintCoinArgs {
 #[derive(Debug, Args)]
 pub struct SpendArgs {
     /// An input to be consumed by this transaction. This argument may be specified multiple times.
-    /// They must all be coins.
+    /// They must all be coins. If omitted entirely, the wallet automatically chooses inputs
+    /// from the owned UTXO set to cover `output_amount`, per `--strategy`.
     #[arg(long, short, verbatim_doc_comment, value_parser = output_ref_from_string)]
     pub input: Vec<OutputRef>,
 
@@ -204,14 +259,48 @@ pub struct SpendArgs {
     // https://docs.rs/clap/latest/clap/_derive/_cookbook/typed_derive/index.html
     // shows how to specify a custom parsing function
     /// Hex encoded address (sr25519 pubkey) of the recipient.
+    /// Kept as a convenience for single-recipient spends; equivalent to one `--to` pair.
     #[arg(long, short, verbatim_doc_comment, value_parser = h256_from_string, default_value = SHAWN_PUB_KEY)]
     pub recipient: H256,
 
     // The `action = Append` allows us to accept the same value multiple times.
-    /// An output amount. For the transaction to be valid, the outputs must add up to less than the sum of the inputs.
+    /// An output amount; every `output_amount` given here pays `recipient`. This argument may
+    /// be specified multiple times to send several outputs to `recipient` in one transaction;
+    /// use `--to` instead to pay other recipients in the same transaction. For the transaction
+    /// to be valid, the outputs must add up to less than the sum of the inputs.
     /// The wallet will not enforce this and will gladly send an invalid which will then be rejected by the node.
     #[arg(long, short, verbatim_doc_comment, action = Append)]
     pub output_amount: Vec<u128>,
+
+    /// An additional `<recipient>:<amount>` pair to pay out in this same transaction.
+    /// This argument may be specified multiple times to split a payment across many recipients.
+    #[arg(long, verbatim_doc_comment, action = Append, value_parser = recipient_amount_from_string)]
+    pub to: Vec<(H256, u128)>,
+
+    /// The coin selection strategy to use when `input` is not given explicitly.
+    #[arg(long, verbatim_doc_comment, value_enum, default_value_t = SelectionStrategy::Bnb)]
+    pub strategy: SelectionStrategy,
+
+    /// The fee rate to pay, in fee units per encoded byte. Used to estimate the fee reserved
+    /// out of the selected inputs when `--fee` is not given explicitly.
+    #[arg(long, verbatim_doc_comment, default_value_t = 1)]
+    pub fee_rate: u128,
+
+    /// An absolute fee to pay, overriding the `--fee-rate` estimate.
+    #[arg(long, verbatim_doc_comment)]
+    pub fee: Option<u128>,
+}
+
+/// Parses a `<H256>:<u128>` pair as used by `SpendArgs::to`.
+fn recipient_amount_from_string(s: &str) -> Result<(H256, u128), String> {
+    let (recipient, amount) = s
+        .split_once(':')
+        .ok_or_else(|| format!("expected `<recipient>:<amount>`, got `{s}`"))?;
+    let recipient = h256_from_string(recipient)?;
+    let amount = amount
+        .parse::<u128>()
+        .map_err(|e| format!("invalid amount `{amount}`: {e}"))?;
+    Ok((recipient, amount))
 }
 
 #[derive(Debug, Args)]
@@ -310,6 +399,15 @@ pub struct BuyKittyArgs {
     /// The wallet will not enforce this and will gladly send an invalid which will then be rejected by the node.
     #[arg(long, short, verbatim_doc_comment, action = Append)]
     pub output_amount: Vec<u128>,
+
+    /// The fee rate to pay, in fee units per encoded byte. Used to estimate the fee reserved
+    /// out of the selected inputs when `--fee` is not given explicitly.
+    #[arg(long, verbatim_doc_comment, default_value_t = 1)]
+    pub fee_rate: u128,
+
+    /// An absolute fee to pay, overriding the `--fee-rate` estimate.
+    #[arg(long, verbatim_doc_comment)]
+    pub fee: Option<u128>,
 }
 
 #[derive(Debug, Args)]
@@ -336,3 +434,82 @@ pub struct MintTradableKittyArgs {
     #[arg(long, short, verbatim_doc_comment, value_parser = h256_from_string, default_value = SHAWN_PUB_KEY)]
     pub owner: H256,
 }
+
+#[derive(Debug, Args)]
+pub struct BuildTransactionArgs {
+    /// An input to be consumed by this transaction. This argument may be specified multiple times.
+    /// They must all be coins. If omitted entirely, the wallet automatically chooses inputs
+    /// from the owned UTXO set to cover `output_amount`, per `--strategy`.
+    #[arg(long, short, verbatim_doc_comment, value_parser = output_ref_from_string)]
+    pub input: Vec<OutputRef>,
+
+    /// Hex encoded address (sr25519 pubkey) of the recipient.
+    /// Kept as a convenience for single-recipient spends; equivalent to one `--to` pair.
+    #[arg(long, short, verbatim_doc_comment, value_parser = h256_from_string, default_value = SHAWN_PUB_KEY)]
+    pub recipient: H256,
+
+    /// An output amount; every `output_amount` given here pays `recipient`. Use `--to` instead
+    /// to pay other recipients in the same transaction.
+    #[arg(long, short, verbatim_doc_comment, action = Append)]
+    pub output_amount: Vec<u128>,
+
+    /// An additional `<recipient>:<amount>` pair to pay out in this same transaction.
+    /// This argument may be specified multiple times to split a payment across many recipients.
+    #[arg(long, verbatim_doc_comment, action = Append, value_parser = recipient_amount_from_string)]
+    pub to: Vec<(H256, u128)>,
+
+    /// The coin selection strategy to use when `input` is not given explicitly.
+    #[arg(long, verbatim_doc_comment, value_enum, default_value_t = SelectionStrategy::Bnb)]
+    pub strategy: SelectionStrategy,
+
+    /// Path to write the unsigned transaction blob to.
+    #[arg(long, verbatim_doc_comment)]
+    pub output: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct SignTransactionArgs {
+    /// Path to the unsigned (or partially-signed) transaction blob to load.
+    #[arg(long, short, verbatim_doc_comment)]
+    pub input: PathBuf,
+
+    /// Path to write the signed transaction blob to. Defaults to overwriting `input`.
+    #[arg(long, short, verbatim_doc_comment)]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct BroadcastTransactionArgs {
+    /// Path to the signed transaction blob to submit to the node.
+    #[arg(long, short, verbatim_doc_comment)]
+    pub input: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct EstimateFeeArgs {
+    /// An input that would be consumed by the transaction. This argument may be specified
+    /// multiple times. They must all be coins.
+    #[arg(long, short, verbatim_doc_comment, value_parser = output_ref_from_string)]
+    pub input: Vec<OutputRef>,
+
+    /// An output amount that the transaction would create. This argument may be specified
+    /// multiple times.
+    #[arg(long, short, verbatim_doc_comment, action = Append)]
+    pub output_amount: Vec<u128>,
+
+    /// The fee rate to estimate with, in fee units per encoded byte.
+    #[arg(long, verbatim_doc_comment, default_value_t = 1)]
+    pub fee_rate: u128,
+}
+
+#[derive(Debug, Args)]
+pub struct SubscribeArgs {
+    /// Comma-separated list of event topics to receive.
+    #[arg(long, verbatim_doc_comment, value_enum, value_delimiter = ',', default_values_t = [Topic::Block, Topic::OwnedOutput, Topic::SpentOutput])]
+    pub topics: Vec<Topic>,
+
+    /// Replay events starting from this block height before streaming live events.
+    /// If not given, only new events are streamed.
+    #[arg(long, verbatim_doc_comment)]
+    pub since: Option<u32>,
+}
@@ -0,0 +1,300 @@
+//! Coin selection strategies used by [`crate::cli::SpendArgs`] when the caller
+//! does not explicitly list the inputs to spend.
+//!
+//! The default strategy is a Branch-and-Bound search over include/exclude
+//! decisions for each candidate UTXO, the same approach used by descriptor
+//! wallets to find a changeless match. When the search finds none within its
+//! effort budget, [`select_coins`] falls back to largest-first accumulation,
+//! which always succeeds given sufficient funds.
+
+use std::fmt;
+
+use clap::ValueEnum;
+use tuxedo_core::types::OutputRef;
+
+/// The maximum number of tree nodes the Branch-and-Bound search will visit
+/// before giving up and falling back to a simpler strategy.
+const BNB_EFFORT_BUDGET: usize = 100_000;
+
+/// A UTXO owned by the wallet that is eligible to be spent.
+#[derive(Debug, Clone, Copy)]
+pub struct CandidateOutput {
+    /// The reference identifying this output on chain.
+    pub output_ref: OutputRef,
+    /// The value of this output, in the chain's native coin.
+    pub amount: u128,
+    /// The height of the block in which this output was created. This is the ordering key
+    /// `SelectionStrategy::OldestFirst` sorts on; it does not depend on the order `candidates`
+    /// happens to be passed in.
+    pub created_at_height: u32,
+}
+
+/// The coin selection strategy to use when the user does not hand-pick inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SelectionStrategy {
+    /// Branch-and-Bound search for a changeless match, falling back to
+    /// largest-first accumulation if no match is found within the effort budget.
+    Bnb,
+    /// Sort candidates by amount, descending, and accumulate until the target is met.
+    LargestFirst,
+    /// Sort candidates by `CandidateOutput::created_at_height` ascending, and accumulate until
+    /// the target is met.
+    OldestFirst,
+}
+
+impl Default for SelectionStrategy {
+    fn default() -> Self {
+        Self::Bnb
+    }
+}
+
+impl fmt::Display for SelectionStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.to_possible_value()
+            .expect("no skipped variants")
+            .get_name()
+            .fmt(f)
+    }
+}
+
+/// The result of a successful coin selection.
+#[derive(Debug, Clone)]
+pub struct Selection {
+    /// The UTXOs chosen to satisfy the spend.
+    pub selected: Vec<CandidateOutput>,
+    /// The amount left over after paying `target`, to be returned to the sender
+    /// as a change output. Zero for a changeless match.
+    pub change: u128,
+}
+
+/// An approximation of the additional fee incurred by adding a change output,
+/// expressed in the same units as `target`. Branch-and-Bound accepts any
+/// match whose leftover is no larger than this.
+pub const DEFAULT_COST_OF_CHANGE: u128 = 1;
+
+/// Select UTXOs from `candidates` that cover `target`, preferring a changeless
+/// match. `candidates` need not be sorted; this function sorts its own working copy.
+///
+/// Returns `None` if `candidates` cannot cover `target` at all.
+pub fn select_coins(
+    candidates: &[CandidateOutput],
+    target: u128,
+    strategy: SelectionStrategy,
+) -> Option<Selection> {
+    match strategy {
+        SelectionStrategy::Bnb => branch_and_bound(candidates, target, DEFAULT_COST_OF_CHANGE)
+            .or_else(|| largest_first(candidates, target)),
+        SelectionStrategy::LargestFirst => largest_first(candidates, target),
+        SelectionStrategy::OldestFirst => oldest_first(candidates, target),
+    }
+}
+
+/// Depth-first search over include/exclude decisions for each candidate,
+/// pruning branches that overshoot `target + cost_of_change` or that can no
+/// longer reach `target` with the candidates remaining. Succeeds only on a
+/// changeless (or near-changeless) match.
+fn branch_and_bound(
+    candidates: &[CandidateOutput],
+    target: u128,
+    cost_of_change: u128,
+) -> Option<Selection> {
+    let mut sorted: Vec<CandidateOutput> = candidates.to_vec();
+    sorted.sort_unstable_by(|a, b| b.amount.cmp(&a.amount));
+
+    let upper_bound = target.checked_add(cost_of_change)?;
+
+    // Suffix sums so we can cheaply bound how much is still reachable from index `i` onward.
+    let mut remaining_sum = vec![0u128; sorted.len() + 1];
+    for i in (0..sorted.len()).rev() {
+        remaining_sum[i] = remaining_sum[i + 1] + sorted[i].amount;
+    }
+
+    let mut effort = 0usize;
+    let mut current = Vec::new();
+    let mut best = None;
+
+    fn search(
+        sorted: &[CandidateOutput],
+        remaining_sum: &[u128],
+        index: usize,
+        running_total: u128,
+        target: u128,
+        upper_bound: u128,
+        effort: &mut usize,
+        current: &mut Vec<CandidateOutput>,
+        best: &mut Option<Selection>,
+    ) {
+        if *effort >= BNB_EFFORT_BUDGET || best.is_some() {
+            return;
+        }
+        *effort += 1;
+
+        if running_total >= target && running_total <= upper_bound {
+            *best = Some(Selection {
+                selected: current.clone(),
+                change: running_total - target,
+            });
+            return;
+        }
+        if running_total > upper_bound {
+            return;
+        }
+        if index == sorted.len() || running_total + remaining_sum[index] < target {
+            return;
+        }
+
+        // Include sorted[index], then try excluding it.
+        current.push(sorted[index]);
+        search(
+            sorted,
+            remaining_sum,
+            index + 1,
+            running_total + sorted[index].amount,
+            target,
+            upper_bound,
+            effort,
+            current,
+            best,
+        );
+        current.pop();
+
+        search(
+            sorted,
+            remaining_sum,
+            index + 1,
+            running_total,
+            target,
+            upper_bound,
+            effort,
+            current,
+            best,
+        );
+    }
+
+    search(
+        &sorted,
+        &remaining_sum,
+        0,
+        0,
+        target,
+        upper_bound,
+        &mut effort,
+        &mut current,
+        &mut best,
+    );
+
+    best
+}
+
+/// Accumulate the largest candidates first until `target` is covered, emitting
+/// whatever is left over as change.
+fn largest_first(candidates: &[CandidateOutput], target: u128) -> Option<Selection> {
+    let mut sorted: Vec<CandidateOutput> = candidates.to_vec();
+    sorted.sort_unstable_by(|a, b| b.amount.cmp(&a.amount));
+    accumulate(sorted, target)
+}
+
+/// Accumulate candidates ordered by `created_at_height` ascending (oldest first) until
+/// `target` is covered, emitting whatever is left over as change.
+fn oldest_first(candidates: &[CandidateOutput], target: u128) -> Option<Selection> {
+    let mut sorted: Vec<CandidateOutput> = candidates.to_vec();
+    sorted.sort_unstable_by_key(|c| c.created_at_height);
+    accumulate(sorted, target)
+}
+
+/// Walk `ordered` in order, selecting outputs until their sum reaches `target`.
+fn accumulate(ordered: Vec<CandidateOutput>, target: u128) -> Option<Selection> {
+    let mut selected = Vec::new();
+    let mut total = 0u128;
+    for candidate in ordered {
+        if total >= target {
+            break;
+        }
+        total += candidate.amount;
+        selected.push(candidate);
+    }
+    if total < target {
+        return None;
+    }
+    Some(Selection {
+        selected,
+        change: total - target,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(amount: u128, created_at_height: u32) -> CandidateOutput {
+        let output_ref = crate::output_ref_from_string(&format!(
+            "0x{:064x}.{created_at_height}",
+            created_at_height
+        ))
+        .expect("valid output ref");
+        CandidateOutput {
+            output_ref,
+            amount,
+            created_at_height,
+        }
+    }
+
+    #[test]
+    fn branch_and_bound_finds_a_changeless_match() {
+        let candidates = [candidate(5, 1), candidate(3, 2), candidate(2, 3)];
+        let selection = select_coins(&candidates, 5, SelectionStrategy::Bnb).unwrap();
+        assert_eq!(selection.change, 0);
+        assert_eq!(
+            selection.selected.iter().map(|c| c.amount).sum::<u128>(),
+            5
+        );
+    }
+
+    #[test]
+    fn branch_and_bound_falls_back_to_largest_first_without_a_changeless_match() {
+        let candidates = [candidate(7, 1), candidate(7, 2)];
+        let selection = select_coins(&candidates, 10, SelectionStrategy::Bnb).unwrap();
+        assert_eq!(selection.selected.len(), 2);
+        assert_eq!(selection.selected[0].amount, 7);
+        assert_eq!(selection.change, 4);
+
+        // Two 7s can't make exactly 12 or 13 (12 + DEFAULT_COST_OF_CHANGE), so BnB should
+        // give up and largest-first should pick both, returning 2 as change.
+        let selection = select_coins(&candidates, 12, SelectionStrategy::Bnb).unwrap();
+        assert_eq!(selection.selected.len(), 2);
+        assert_eq!(selection.change, 2);
+    }
+
+    #[test]
+    fn largest_first_prefers_bigger_utxos() {
+        let candidates = [candidate(1, 1), candidate(10, 2), candidate(2, 3)];
+        let selection = select_coins(&candidates, 8, SelectionStrategy::LargestFirst).unwrap();
+        assert_eq!(selection.selected.len(), 1);
+        assert_eq!(selection.selected[0].amount, 10);
+        assert_eq!(selection.change, 2);
+    }
+
+    #[test]
+    fn oldest_first_ignores_input_order_and_sorts_by_height() {
+        // Deliberately passed in neither ascending nor descending height order; the
+        // selection must still proceed oldest-height first.
+        let candidates = [candidate(5, 30), candidate(5, 10), candidate(5, 20)];
+        let selection = select_coins(&candidates, 8, SelectionStrategy::OldestFirst).unwrap();
+        assert_eq!(
+            selection
+                .selected
+                .iter()
+                .map(|c| c.created_at_height)
+                .collect::<Vec<_>>(),
+            vec![10, 20]
+        );
+    }
+
+    #[test]
+    fn none_when_candidates_cannot_cover_target() {
+        let candidates = [candidate(1, 1), candidate(2, 2)];
+        assert!(select_coins(&candidates, 100, SelectionStrategy::Bnb).is_none());
+        assert!(select_coins(&candidates, 100, SelectionStrategy::LargestFirst).is_none());
+        assert!(select_coins(&candidates, 100, SelectionStrategy::OldestFirst).is_none());
+    }
+}
@@ -0,0 +1,16 @@
+//! Compact descriptor expansion for watch-only tracking.
+//!
+//! A descriptor is a colon-separated list of hex-encoded sr25519 public
+//! keys, e.g. `"0xabc...:0xdef..."`. `ImportDescriptor` expands one into the
+//! set of public keys it names so they can all be registered as watch-only
+//! in a single command, mirroring how descriptor wallets separate key
+//! tracking from signing capability.
+
+use sp_core::H256;
+
+use crate::h256_from_string;
+
+/// Expand a descriptor string into the public keys it names.
+pub fn expand_descriptor(descriptor: &str) -> Result<Vec<H256>, String> {
+    descriptor.split(':').map(h256_from_string).collect()
+}
@@ -0,0 +1,85 @@
+//! Fee estimation helpers shared by `SpendCoins`, `BuyKitty`, and the
+//! standalone `EstimateFee` command.
+//!
+//! Tuxedo transactions are SCALE-encoded, so a reasonable size estimate is
+//! the encoded length of the inputs, outputs, and one redeemer signature per
+//! input, without needing to actually construct and sign the transaction.
+
+/// The size, in bytes, of a single sr25519 signature placeholder reserved
+/// for a redeemer.
+const REDEEMER_SIZE_BYTES: u64 = 64;
+
+/// Fixed per-output overhead, in bytes, covering the owner pubkey and the
+/// SCALE length/discriminant framing around it.
+const OUTPUT_OVERHEAD_BYTES: u64 = 40;
+
+/// Fixed per-input overhead, in bytes, covering the `OutputRef` itself.
+const INPUT_OVERHEAD_BYTES: u64 = 40;
+
+/// Estimate the encoded size, in bytes, of a transaction with `input_count`
+/// inputs and `output_count` outputs.
+pub fn estimate_tx_size(input_count: usize, output_count: usize) -> u64 {
+    input_count as u64 * (INPUT_OVERHEAD_BYTES + REDEEMER_SIZE_BYTES)
+        + output_count as u64 * OUTPUT_OVERHEAD_BYTES
+}
+
+/// Estimate the fee for a transaction with `input_count` inputs and
+/// `output_count` outputs at the given `fee_rate` (units per byte).
+pub fn estimate_fee(input_count: usize, output_count: usize, fee_rate: u128) -> u128 {
+    estimate_tx_size(input_count, output_count) as u128 * fee_rate
+}
+
+/// Resolve the fee to actually reserve for a transaction, given the optional
+/// `--fee` override and `--fee-rate`-derived estimate. An explicit `--fee`
+/// always wins; otherwise the rate-based estimate is used.
+///
+/// `produces_change` must be `true` whenever coin selection (see
+/// `coin_select`) may add a change output back to the sender, which is the
+/// common case any time inputs aren't chosen to match the target exactly.
+/// Passing `false` when change is in fact produced underfunds the fee by
+/// one output's worth of bytes.
+pub fn resolve_fee(
+    explicit_fee: Option<u128>,
+    fee_rate: u128,
+    input_count: usize,
+    output_count: usize,
+    produces_change: bool,
+) -> u128 {
+    let output_count = output_count + usize::from(produces_change);
+    explicit_fee.unwrap_or_else(|| estimate_fee(input_count, output_count, fee_rate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_scales_with_inputs_and_outputs() {
+        let one_in_one_out = estimate_tx_size(1, 1);
+        assert_eq!(
+            estimate_tx_size(2, 1),
+            one_in_one_out + INPUT_OVERHEAD_BYTES + REDEEMER_SIZE_BYTES
+        );
+        assert_eq!(estimate_tx_size(1, 2), one_in_one_out + OUTPUT_OVERHEAD_BYTES);
+    }
+
+    #[test]
+    fn fee_is_size_times_rate() {
+        assert_eq!(
+            estimate_fee(1, 1, 3),
+            estimate_tx_size(1, 1) as u128 * 3
+        );
+    }
+
+    #[test]
+    fn explicit_fee_overrides_the_rate_estimate() {
+        assert_eq!(resolve_fee(Some(42), 1_000_000, 5, 5, true), 42);
+    }
+
+    #[test]
+    fn resolve_fee_reserves_for_a_change_output_when_one_may_be_produced() {
+        let without_change = resolve_fee(None, 1, 1, 1, false);
+        let with_change = resolve_fee(None, 1, 1, 1, true);
+        assert_eq!(with_change - without_change, OUTPUT_OVERHEAD_BYTES as u128);
+    }
+}
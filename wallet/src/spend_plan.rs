@@ -0,0 +1,163 @@
+//! Turns `SpendArgs`/`BuildTransactionArgs` into a concrete set of inputs,
+//! outputs, and a reserved fee, by wiring together [`coin_select::select_coins`]
+//! and [`fee::resolve_fee`]. This is the planning step that `SpendCoins` and
+//! `BuildTransaction` share: both need to pick inputs (when the user didn't
+//! name them explicitly) and work out how much fee to carve out of them
+//! before handing inputs/outputs off to signing.
+
+use sp_core::H256;
+
+use crate::{
+    coin_select::{self, CandidateOutput, SelectionStrategy},
+    fee,
+};
+
+/// The resolved inputs, outputs, and fee for a spend, ready to be handed to
+/// `SignTransaction` (via `BuildTransaction`) or signed and submitted
+/// directly (via `SpendCoins`).
+#[derive(Debug, Clone)]
+pub struct SpendPlan {
+    /// The UTXOs selected to cover `outputs` plus the fee.
+    pub inputs: Vec<CandidateOutput>,
+    /// The recipient outputs requested by the caller (does not include change).
+    pub outputs: Vec<(H256, u128)>,
+    /// The fee reserved out of `inputs`.
+    pub fee: u128,
+    /// The change returned to the sender, if automatic coin selection chose
+    /// inputs that overshot `outputs` plus `fee`. Zero when the caller
+    /// supplied `explicit_inputs` themselves.
+    pub change: u128,
+}
+
+/// Build a [`SpendPlan`] for `outputs`, either using `explicit_inputs` as-is
+/// (when the caller named inputs with `--input`) or selecting from
+/// `candidates` automatically via `strategy`.
+///
+/// When selecting automatically, returns an error if `candidates` can't cover
+/// `outputs` plus the estimated fee. `explicit_inputs` are not checked against
+/// `outputs` plus the fee.
+pub fn plan_spend(
+    candidates: &[CandidateOutput],
+    explicit_inputs: &[CandidateOutput],
+    outputs: Vec<(H256, u128)>,
+    strategy: SelectionStrategy,
+    fee_rate: u128,
+    explicit_fee: Option<u128>,
+) -> anyhow::Result<SpendPlan> {
+    let output_total: u128 = outputs.iter().map(|(_, amount)| *amount).sum();
+
+    if !explicit_inputs.is_empty() {
+        // The caller named their own inputs; there is no automatic change output to plan for,
+        // since whatever isn't spent or paid as fee is left for the caller to account for.
+        let fee = fee::resolve_fee(
+            explicit_fee,
+            fee_rate,
+            explicit_inputs.len(),
+            outputs.len(),
+            false,
+        );
+        return Ok(SpendPlan {
+            inputs: explicit_inputs.to_vec(),
+            outputs,
+            fee,
+            change: 0,
+        });
+    }
+
+    // A first fee estimate assuming a change output, since Branch-and-Bound falling back to
+    // largest-first is the common case; `select_coins` may still land on a changeless match,
+    // in which case we fix the fee (and therefore the target) up below.
+    let provisional_fee = fee::resolve_fee(
+        explicit_fee,
+        fee_rate,
+        candidates.len().min(outputs.len().max(1)),
+        outputs.len(),
+        true,
+    );
+    let target = output_total
+        .checked_add(provisional_fee)
+        .ok_or_else(|| anyhow::anyhow!("target amount overflowed a u128"))?;
+
+    let selection = coin_select::select_coins(candidates, target, strategy)
+        .ok_or_else(|| anyhow::anyhow!("insufficient funds to cover outputs plus fee"))?;
+
+    let fee = fee::resolve_fee(
+        explicit_fee,
+        fee_rate,
+        selection.selected.len(),
+        outputs.len(),
+        selection.change > 0,
+    );
+
+    Ok(SpendPlan {
+        inputs: selection.selected,
+        outputs,
+        fee,
+        change: selection.change,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(amount: u128, created_at_height: u32) -> CandidateOutput {
+        let output_ref = crate::output_ref_from_string(&format!(
+            "0x{:064x}.{created_at_height}",
+            created_at_height
+        ))
+        .expect("valid output ref");
+        CandidateOutput {
+            output_ref,
+            amount,
+            created_at_height,
+        }
+    }
+
+    #[test]
+    fn explicit_inputs_are_used_as_is_with_no_change() {
+        let inputs = [candidate(100, 1)];
+        let plan = plan_spend(
+            &[],
+            &inputs,
+            vec![(H256::repeat_byte(1), 50)],
+            SelectionStrategy::Bnb,
+            1,
+            None,
+        )
+        .unwrap();
+        assert_eq!(plan.inputs.len(), 1);
+        assert_eq!(plan.change, 0);
+    }
+
+    #[test]
+    fn automatic_selection_reserves_a_fee_and_may_produce_change() {
+        let candidates = [candidate(1_000, 1)];
+        let plan = plan_spend(
+            &candidates,
+            &[],
+            vec![(H256::repeat_byte(2), 10)],
+            SelectionStrategy::LargestFirst,
+            1,
+            None,
+        )
+        .unwrap();
+        assert_eq!(plan.inputs.len(), 1);
+        assert!(plan.fee > 0);
+        assert_eq!(plan.change, 1_000 - 10 - plan.fee);
+    }
+
+    #[test]
+    fn errors_when_funds_cannot_cover_outputs_plus_fee() {
+        let candidates = [candidate(5, 1)];
+        let result = plan_spend(
+            &candidates,
+            &[],
+            vec![(H256::repeat_byte(3), 1_000)],
+            SelectionStrategy::Bnb,
+            1,
+            None,
+        );
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,266 @@
+//! Live event feed consumed by the `Subscribe` command, analogous to the ZMQ
+//! `pubhashblock`/`pubrawtx` notification topics exposed by full nodes.
+//!
+//! Rather than polling `ShowBalance`/`ShowAllKitties` in a loop, downstream
+//! tooling opens a [`Subscribe`](crate::cli::Command::Subscribe) stream by
+//! calling [`EventBus::subscribe`] and reading [`StreamEvent`]s off the
+//! returned [`Subscription`] until it is dropped or the connection closes.
+//! The sync loop (wherever it observes new blocks, newly-created owned
+//! outputs, and newly-spent outputs) is the sole expected caller of
+//! [`EventBus::publish`].
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use clap::ValueEnum;
+use sp_core::H256;
+use tokio::sync::broadcast;
+use tuxedo_core::types::OutputRef;
+
+/// How many past events the bus retains in memory to serve `--since` replay
+/// and to let a subscriber that falls behind the live channel catch back up.
+/// Older events age out once this many newer ones have been published.
+const HISTORY_CAPACITY: usize = 4096;
+
+/// How many not-yet-delivered events a subscriber may fall behind by before
+/// `tokio::sync::broadcast` starts dropping its backlog. When that happens,
+/// [`Subscription::recv`] refills its replay queue from the retained
+/// history rather than losing the skipped events outright; events older
+/// than `HISTORY_CAPACITY` are still gone for good.
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// A category of event the wallet can push to subscribers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Topic {
+    /// A new block header was synced.
+    Block,
+    /// A new output owned by a tracked key was created.
+    OwnedOutput,
+    /// A previously tracked output was spent.
+    SpentOutput,
+}
+
+/// An event pushed to a `Subscribe` stream.
+#[derive(Debug, Clone)]
+pub enum Event {
+    /// A new block was synced, identified by height and hash.
+    Block { height: u32, hash: H256 },
+    /// A new output owned by a tracked key was created.
+    OwnedOutput {
+        output_ref: OutputRef,
+        owner: H256,
+        amount: u128,
+    },
+    /// A previously tracked output was spent.
+    SpentOutput { output_ref: OutputRef },
+}
+
+impl Event {
+    /// The topic this event belongs to, used to apply `--topics` filtering.
+    pub fn topic(&self) -> Topic {
+        match self {
+            Event::Block { .. } => Topic::Block,
+            Event::OwnedOutput { .. } => Topic::OwnedOutput,
+            Event::SpentOutput { .. } => Topic::SpentOutput,
+        }
+    }
+}
+
+/// An [`Event`] tagged with the block height the wallet had synced to when
+/// it was published, so that `--since <block_height>` replay can filter on it.
+#[derive(Debug, Clone)]
+pub struct StreamEvent {
+    pub height: u32,
+    pub event: Event,
+}
+
+/// The in-process hub that the sync loop publishes events to and that
+/// `Subscribe` reads from. A single `EventBus` is shared (e.g. behind an
+/// `Arc`) between the sync task and every open subscription; each
+/// subscription gets its own filtered view via [`EventBus::subscribe`].
+pub struct EventBus {
+    sender: broadcast::Sender<StreamEvent>,
+    history: Arc<Mutex<VecDeque<StreamEvent>>>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, _receiver) = broadcast::channel(CHANNEL_CAPACITY);
+        Self {
+            sender,
+            history: Arc::new(Mutex::new(VecDeque::with_capacity(HISTORY_CAPACITY))),
+        }
+    }
+
+    /// Publish an event at the given sync height to every current and future
+    /// subscriber, and record it for `--since` replay.
+    pub fn publish(&self, height: u32, event: Event) {
+        let stream_event = StreamEvent { height, event };
+
+        let mut history = self.history.lock().expect("history mutex poisoned");
+        if history.len() == HISTORY_CAPACITY {
+            history.pop_front();
+        }
+        history.push_back(stream_event.clone());
+
+        // An error here just means there are no subscribers listening right now.
+        // Sent while still holding the history lock, so `subscribe()` sees this event
+        // either in its replay snapshot or on its live receiver, not both.
+        let _ = self.sender.send(stream_event);
+        drop(history);
+    }
+
+    /// Open a subscription filtered to `topics`, first replaying any
+    /// retained history at or after `since` (when given), then delivering
+    /// live events as they're published.
+    ///
+    /// The live receiver is created while still holding the history lock, so
+    /// that a `publish()` racing this call either lands entirely before the
+    /// replay snapshot (and is replayed) or entirely after the receiver
+    /// exists (and is delivered live) — never in the gap between the two.
+    pub fn subscribe(&self, topics: Vec<Topic>, since: Option<u32>) -> Subscription {
+        let history = self.history.lock().expect("history mutex poisoned");
+        let receiver = self.sender.subscribe();
+        let replay = history
+            .iter()
+            .filter(|e| topics.contains(&e.event.topic()))
+            .filter(|e| since.map_or(true, |since| e.height >= since))
+            .cloned()
+            .collect();
+        let last_delivered_height = history.back().map(|e| e.height).or(since);
+        drop(history);
+
+        Subscription {
+            replay,
+            receiver,
+            topics,
+            history: Arc::clone(&self.history),
+            last_delivered_height,
+        }
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A single subscriber's view onto an [`EventBus`]: replayed history first,
+/// then live events, both filtered to the topics it asked for.
+pub struct Subscription {
+    replay: VecDeque<StreamEvent>,
+    receiver: broadcast::Receiver<StreamEvent>,
+    topics: Vec<Topic>,
+    history: Arc<Mutex<VecDeque<StreamEvent>>>,
+    last_delivered_height: Option<u32>,
+}
+
+impl Subscription {
+    /// Wait for and return the next event due to this subscriber, or `None`
+    /// once the bus has shut down.
+    pub async fn recv(&mut self) -> Option<StreamEvent> {
+        if let Some(event) = self.replay.pop_front() {
+            self.last_delivered_height = Some(event.height);
+            return Some(event);
+        }
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) if self.topics.contains(&event.event.topic()) => {
+                    self.last_delivered_height = Some(event.height);
+                    return Some(event);
+                }
+                Ok(_) => continue,
+                Err(broadcast::error::RecvError::Lagged(_)) => {
+                    // We fell behind the live channel and skipped some events. Refill the
+                    // replay queue from retained history instead of losing them outright.
+                    self.recover_from_lag();
+                    if let Some(event) = self.replay.pop_front() {
+                        self.last_delivered_height = Some(event.height);
+                        return Some(event);
+                    }
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Rebuild the replay queue from retained history for every event after
+    /// the last one this subscriber actually received, matching its topics.
+    fn recover_from_lag(&mut self) {
+        let history = self.history.lock().expect("history mutex poisoned");
+        self.replay = history
+            .iter()
+            .filter(|e| self.topics.contains(&e.event.topic()))
+            .filter(|e| self.last_delivered_height.map_or(true, |height| e.height > height))
+            .cloned()
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block_event(height: u32) -> Event {
+        Event::Block {
+            height,
+            hash: H256::repeat_byte(height as u8),
+        }
+    }
+
+    #[tokio::test]
+    async fn replays_history_since_the_given_height() {
+        let bus = EventBus::new();
+        bus.publish(1, block_event(1));
+        bus.publish(2, block_event(2));
+        bus.publish(3, block_event(3));
+
+        let mut sub = bus.subscribe(vec![Topic::Block], Some(2));
+        assert_eq!(sub.recv().await.unwrap().height, 2);
+        assert_eq!(sub.recv().await.unwrap().height, 3);
+    }
+
+    #[tokio::test]
+    async fn filters_by_topic() {
+        let bus = EventBus::new();
+        let output_ref = crate::output_ref_from_string(
+            "0x0000000000000000000000000000000000000000000000000000000000000000.0",
+        )
+        .expect("valid output ref");
+        bus.publish(1, Event::SpentOutput { output_ref });
+        bus.publish(2, block_event(2));
+
+        let mut sub = bus.subscribe(vec![Topic::Block], None);
+        let event = sub.recv().await.unwrap();
+        assert_eq!(event.event.topic(), Topic::Block);
+    }
+
+    #[tokio::test]
+    async fn delivers_events_published_after_subscribing() {
+        let bus = EventBus::new();
+        let mut sub = bus.subscribe(vec![Topic::Block], None);
+
+        bus.publish(1, block_event(1));
+        assert_eq!(sub.recv().await.unwrap().height, 1);
+    }
+
+    #[tokio::test]
+    async fn recovers_lagged_events_from_history_instead_of_dropping_them() {
+        let bus = EventBus::new();
+        let mut sub = bus.subscribe(vec![Topic::Block], None);
+
+        // Publish far more events than the broadcast channel's capacity without the
+        // subscriber reading any of them, forcing it to lag.
+        for height in 1..=(CHANNEL_CAPACITY as u32 * 2) {
+            bus.publish(height, block_event(height));
+        }
+
+        let first = sub.recv().await.unwrap();
+        // Nothing earlier than `first` was silently lost and re-delivered out of order.
+        for expected in (first.height + 1)..=(CHANNEL_CAPACITY as u32 * 2) {
+            assert_eq!(sub.recv().await.unwrap().height, expected);
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Offline build/sign/broadcast workflow, modeled on the partially-signed
+//! transaction pattern used by descriptor wallets.
+//!
+//! [`BuildTransaction`](crate::cli::Command::BuildTransaction) produces an
+//! [`UnsignedTransactionBlob`] and writes it to disk. `SignTransaction` loads
+//! it, fills in redeemer signatures using keys from the local keystore (no
+//! node connection required), and writes back a [`SignedTransactionBlob`].
+//! `BroadcastTransaction` then submits the signed blob to the node.
+//!
+//! These blobs deliberately model a transaction as `(inputs, outputs,
+//! redeemers)` rather than embedding the runtime's concrete
+//! `tuxedo_core::types::Transaction<OuterVerifier, OuterConstraintChecker>`.
+//! That alias is generic over the runtime's verifier and constraint-checker
+//! types, neither of which this crate names anywhere else (the wallet talks
+//! to the node over RPC using already-encoded bytes); hard-coding a guess at
+//! that import here would be more fragile than this reduced form. Submitting
+//! a [`SignedTransactionBlob`] therefore requires one more conversion step,
+//! at the RPC boundary, into the runtime's `Transaction` type using the same
+//! SCALE encoding these blobs already round-trip through.
+
+use std::{fs, path::Path};
+
+use parity_scale_codec::{Decode, Encode};
+use sp_core::{sr25519::Signature, H256};
+use tuxedo_core::types::OutputRef;
+
+/// An unsigned Tuxedo transaction, serialized to a file for offline or
+/// multi-party signing. Carries everything needed to produce signatures
+/// except the private keys themselves.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct UnsignedTransactionBlob {
+    /// The inputs this transaction will consume.
+    pub inputs: Vec<OutputRef>,
+    /// The `(owner, amount)` outputs this transaction will create.
+    pub outputs: Vec<(H256, u128)>,
+    /// One placeholder per input, filled in by `SignTransaction` once the
+    /// corresponding key is found in the keystore.
+    pub redeemer_placeholders: Vec<Option<Signature>>,
+}
+
+/// A Tuxedo transaction blob whose redeemers have all been filled in and is
+/// ready to submit with `BroadcastTransaction`.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct SignedTransactionBlob {
+    /// The inputs this transaction consumes.
+    pub inputs: Vec<OutputRef>,
+    /// The `(owner, amount)` outputs this transaction creates.
+    pub outputs: Vec<(H256, u128)>,
+    /// The sr25519 signature authorizing each input, in input order.
+    pub redeemers: Vec<Signature>,
+}
+
+impl UnsignedTransactionBlob {
+    /// Create a fresh unsigned blob with one placeholder per input, ready to
+    /// be signed.
+    pub fn new(inputs: Vec<OutputRef>, outputs: Vec<(H256, u128)>) -> Self {
+        let redeemer_placeholders = vec![None; inputs.len()];
+        Self {
+            inputs,
+            outputs,
+            redeemer_placeholders,
+        }
+    }
+
+    /// Write this blob to `path` as hex-encoded SCALE.
+    pub fn write_to_file(&self, path: &Path) -> std::io::Result<()> {
+        fs::write(path, hex::encode(self.encode()))
+    }
+
+    /// Load an unsigned blob previously written by [`Self::write_to_file`].
+    pub fn read_from_file(path: &Path) -> anyhow::Result<Self> {
+        let hex_contents = fs::read_to_string(path)?;
+        let bytes = hex::decode(hex_contents.trim())?;
+        Ok(Self::decode(&mut &bytes[..])?)
+    }
+
+    /// True once every input has a redeemer filled in.
+    pub fn fully_signed(&self) -> bool {
+        self.redeemer_placeholders.iter().all(Option::is_some)
+    }
+
+    /// Convert to a [`SignedTransactionBlob`], assuming every placeholder has
+    /// been filled in. Returns `None` otherwise.
+    pub fn into_signed(self) -> Option<SignedTransactionBlob> {
+        let redeemers = self
+            .redeemer_placeholders
+            .into_iter()
+            .collect::<Option<Vec<_>>>()?;
+        Some(SignedTransactionBlob {
+            inputs: self.inputs,
+            outputs: self.outputs,
+            redeemers,
+        })
+    }
+}
+
+impl SignedTransactionBlob {
+    /// Write this blob to `path` as hex-encoded SCALE.
+    pub fn write_to_file(&self, path: &Path) -> std::io::Result<()> {
+        fs::write(path, hex::encode(self.encode()))
+    }
+
+    /// Load a signed blob previously written by [`Self::write_to_file`].
+    pub fn read_from_file(path: &Path) -> anyhow::Result<Self> {
+        let hex_contents = fs::read_to_string(path)?;
+        let bytes = hex::decode(hex_contents.trim())?;
+        Ok(Self::decode(&mut &bytes[..])?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("tuxedo-wallet-test-{name}-{:?}", std::thread::current().id()))
+    }
+
+    fn sample_output_ref(index: u32) -> OutputRef {
+        crate::output_ref_from_string(&format!("0x{:064x}.{index}", index)).expect("valid output ref")
+    }
+
+    #[test]
+    fn unsigned_blob_round_trips_through_a_file() {
+        let blob = UnsignedTransactionBlob::new(
+            vec![sample_output_ref(0), sample_output_ref(1)],
+            vec![(H256::repeat_byte(7), 100)],
+        );
+        let path = temp_file_path("unsigned");
+        blob.write_to_file(&path).unwrap();
+        let loaded = UnsignedTransactionBlob::read_from_file(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(blob, loaded);
+        assert!(!loaded.fully_signed());
+    }
+
+    #[test]
+    fn signed_blob_round_trips_through_a_file() {
+        let blob = SignedTransactionBlob {
+            inputs: vec![sample_output_ref(0)],
+            outputs: vec![(H256::repeat_byte(9), 50)],
+            redeemers: vec![Signature::from_raw([1u8; 64])],
+        };
+        let path = temp_file_path("signed");
+        blob.write_to_file(&path).unwrap();
+        let loaded = SignedTransactionBlob::read_from_file(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(blob, loaded);
+    }
+
+    #[test]
+    fn into_signed_requires_every_placeholder_filled_in() {
+        let mut blob =
+            UnsignedTransactionBlob::new(vec![sample_output_ref(0), sample_output_ref(1)], vec![]);
+        assert!(blob.clone().into_signed().is_none());
+
+        blob.redeemer_placeholders[0] = Some(Signature::from_raw([2u8; 64]));
+        assert!(blob.clone().into_signed().is_none());
+
+        blob.redeemer_placeholders[1] = Some(Signature::from_raw([3u8; 64]));
+        assert!(blob.fully_signed());
+        assert!(blob.into_signed().is_some());
+    }
+}
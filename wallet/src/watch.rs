@@ -0,0 +1,245 @@
+//! Watch-only key tracking.
+//!
+//! `WatchAddress`/`UnwatchAddress`/`ImportDescriptor` register and remove
+//! public keys here without ever touching the keystore, so the wallet can
+//! index balances and kitty ownership for cold addresses and third-party
+//! owners it cannot sign for. [`WatchRegistry::tracked_keys`] is what
+//! `ShowBalance`, `ShowOwnedKitties`, and `ShowAllOutputs` fold in alongside
+//! the keystore's own keys, and [`WatchRegistry::merge_for_display`] is what
+//! `ShowKeys` uses to flag watch-only entries as unspendable.
+
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use sp_core::H256;
+
+use crate::{descriptor::expand_descriptor, h256_from_string};
+
+/// File (one hex-encoded public key per line) that watch-only keys are
+/// persisted to inside the wallet's data directory.
+const WATCH_ONLY_FILENAME: &str = "watch_only_keys";
+
+/// Whether a key tracked by the wallet can sign transactions or is
+/// watch-only. Used by `ShowKeys` to flag unspendable entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyKind {
+    /// Private material for this key lives in the keystore.
+    Spendable,
+    /// Only the public key is known; the wallet cannot sign for it.
+    WatchOnly,
+}
+
+/// A key tracked by the wallet, annotated with whether it's spendable, as
+/// shown by `ShowKeys`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayKey {
+    pub pub_key: H256,
+    pub kind: KeyKind,
+}
+
+/// The set of public keys tracked for indexing without a corresponding
+/// keystore entry.
+pub struct WatchRegistry {
+    path: PathBuf,
+    keys: BTreeSet<H256>,
+}
+
+impl WatchRegistry {
+    /// Load the registry from `wallet_path`'s data directory, or start empty
+    /// if it has never been written to.
+    pub fn load(wallet_path: &Path) -> anyhow::Result<Self> {
+        let path = wallet_path.join(WATCH_ONLY_FILENAME);
+        let keys = if path.exists() {
+            fs::read_to_string(&path)?
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(|line| h256_from_string(line).map_err(anyhow::Error::msg))
+                .collect::<anyhow::Result<BTreeSet<_>>>()?
+        } else {
+            BTreeSet::new()
+        };
+        Ok(Self { path, keys })
+    }
+
+    /// Persist the current set of watch-only keys, one hex-encoded key per
+    /// line, in the same `0x`-prefixed format `h256_from_string` parses
+    /// (rather than `H256`'s `Debug` output, which is not a format contract).
+    fn save(&self) -> std::io::Result<()> {
+        let contents = self
+            .keys
+            .iter()
+            .map(|key| format!("0x{}", hex::encode(key.as_bytes())))
+            .collect::<Vec<_>>()
+            .join("\n");
+        fs::write(&self.path, contents)
+    }
+
+    /// Register `pub_key` as watch-only. Returns `true` if it was newly
+    /// added, or an error if `pub_key` already has private material in the
+    /// keystore (a spendable key should never also show up flagged
+    /// watch-only).
+    pub fn watch(&mut self, pub_key: H256, spendable_keys: &[H256]) -> anyhow::Result<bool> {
+        if spendable_keys.contains(&pub_key) {
+            anyhow::bail!(
+                "{pub_key:?} already has a private key in the keystore; it is spendable, not watch-only"
+            );
+        }
+        let inserted = self.keys.insert(pub_key);
+        if inserted {
+            self.save()?;
+        }
+        Ok(inserted)
+    }
+
+    /// Stop tracking `pub_key`. Returns `true` if it had been tracked.
+    pub fn unwatch(&mut self, pub_key: &H256) -> std::io::Result<bool> {
+        let removed = self.keys.remove(pub_key);
+        if removed {
+            self.save()?;
+        }
+        Ok(removed)
+    }
+
+    /// Expand `descriptor` and register every public key it names as
+    /// watch-only, silently skipping any that are already spendable in the
+    /// keystore. Returns the keys that were newly added.
+    pub fn import_descriptor(
+        &mut self,
+        descriptor: &str,
+        spendable_keys: &[H256],
+    ) -> anyhow::Result<Vec<H256>> {
+        let keys = expand_descriptor(descriptor).map_err(anyhow::Error::msg)?;
+        let newly_added = keys
+            .into_iter()
+            .filter(|key| !spendable_keys.contains(key))
+            .filter_map(|key| self.keys.insert(key).then_some(key))
+            .collect::<Vec<_>>();
+        if !newly_added.is_empty() {
+            self.save()?;
+        }
+        Ok(newly_added)
+    }
+
+    /// True if `pub_key` is tracked as watch-only (as opposed to spendable).
+    pub fn is_watch_only(&self, pub_key: &H256) -> bool {
+        self.keys.contains(pub_key)
+    }
+
+    /// All keys that `ShowBalance`, `ShowOwnedKitties`, and `ShowAllOutputs`
+    /// should index against: the keystore's own spendable keys plus every
+    /// watch-only key registered here.
+    pub fn tracked_keys(&self, spendable_keys: &[H256]) -> Vec<H256> {
+        let mut all: BTreeSet<H256> = self.keys.clone();
+        all.extend(spendable_keys.iter().copied());
+        all.into_iter().collect()
+    }
+
+    /// Combine `spendable_keys` (from the keystore) with the watch-only set
+    /// for `ShowKeys` display, flagging each as spendable or unspendable. A
+    /// key present in both sets is shown once, as spendable: `watch()` and
+    /// `import_descriptor()` already keep this from happening in practice,
+    /// but a key can still be inserted into the keystore after it was
+    /// watched, so this is deduplicated here too rather than assumed.
+    pub fn merge_for_display(&self, spendable_keys: &[H256]) -> Vec<DisplayKey> {
+        let mut display: Vec<DisplayKey> = spendable_keys
+            .iter()
+            .map(|&pub_key| DisplayKey {
+                pub_key,
+                kind: KeyKind::Spendable,
+            })
+            .collect();
+        display.extend(
+            self.keys
+                .iter()
+                .filter(|key| !spendable_keys.contains(key))
+                .map(|&pub_key| DisplayKey {
+                    pub_key,
+                    kind: KeyKind::WatchOnly,
+                }),
+        );
+        display
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_wallet_dir(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "tuxedo-wallet-test-watch-{name}-{:?}",
+            std::thread::current().id()
+        ));
+        fs::create_dir_all(&path).unwrap();
+        path
+    }
+
+    #[test]
+    fn watch_and_unwatch_round_trip_through_disk() {
+        let dir = temp_wallet_dir("round-trip");
+        let key = H256::repeat_byte(1);
+
+        let mut registry = WatchRegistry::load(&dir).unwrap();
+        assert!(registry.watch(key, &[]).unwrap());
+        assert!(registry.is_watch_only(&key));
+
+        // Reload from disk to make sure `save`'s format is actually what `load` expects.
+        let reloaded = WatchRegistry::load(&dir).unwrap();
+        assert!(reloaded.is_watch_only(&key));
+
+        let mut reloaded = reloaded;
+        assert!(reloaded.unwatch(&key).unwrap());
+        assert!(!WatchRegistry::load(&dir).unwrap().is_watch_only(&key));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn watching_a_spendable_key_is_rejected() {
+        let dir = temp_wallet_dir("reject-spendable");
+        let key = H256::repeat_byte(2);
+        let mut registry = WatchRegistry::load(&dir).unwrap();
+
+        assert!(registry.watch(key, &[key]).is_err());
+        assert!(!registry.is_watch_only(&key));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn merge_for_display_does_not_duplicate_a_key_that_is_both() {
+        let dir = temp_wallet_dir("dedup-display");
+        let key = H256::repeat_byte(3);
+        let mut registry = WatchRegistry::load(&dir).unwrap();
+        assert!(registry.watch(key, &[]).unwrap());
+
+        // Simulate the key having since been imported into the keystore.
+        let display = registry.merge_for_display(&[key]);
+        assert_eq!(display.len(), 1);
+        assert_eq!(display[0].kind, KeyKind::Spendable);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn import_descriptor_skips_already_spendable_keys() {
+        let dir = temp_wallet_dir("import-skip-spendable");
+        let spendable = H256::repeat_byte(4);
+        let watch_only = H256::repeat_byte(5);
+        let descriptor = format!("{spendable:?}:{watch_only:?}");
+
+        let mut registry = WatchRegistry::load(&dir).unwrap();
+        let added = registry
+            .import_descriptor(&descriptor, &[spendable])
+            .unwrap();
+
+        assert_eq!(added, vec![watch_only]);
+        assert!(!registry.is_watch_only(&spendable));
+        assert!(registry.is_watch_only(&watch_only));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}